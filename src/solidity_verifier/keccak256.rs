@@ -0,0 +1,111 @@
+use super::codegen::SolidityTranscript;
+use crate::transcript::sha256::ShaRead;
+use halo2_proofs::arithmetic::CurveAffine;
+use halo2_proofs::pairing::group::ff::PrimeField;
+use halo2_proofs::transcript::Challenge255;
+use halo2_proofs::transcript::EncodedChallenge;
+use halo2_proofs::transcript::Transcript;
+use halo2_proofs::transcript::TranscriptRead;
+use sha3::Digest;
+use std::io;
+use std::io::Read;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct SolidityKeccakRead<C: CurveAffine, E: EncodedChallenge<C>> {
+    _phantom: PhantomData<(C, E)>,
+}
+
+impl<C: CurveAffine, E: EncodedChallenge<C>> SolidityKeccakRead<C, E> {
+    pub fn init() -> Self {
+        SolidityKeccakRead::<C, E> {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<C: CurveAffine> TranscriptRead<C, Challenge255<C>> for SolidityKeccakRead<C, Challenge255<C>> {
+    fn read_point(&mut self) -> io::Result<C> {
+        Ok(C::identity())
+    }
+
+    fn read_scalar(&mut self) -> io::Result<C::Scalar> {
+        Ok(C::Scalar::root_of_unity())
+    }
+}
+
+impl<C: CurveAffine> Transcript<C, Challenge255<C>> for SolidityKeccakRead<C, Challenge255<C>> {
+    fn squeeze_challenge(&mut self) -> Challenge255<C> {
+        let mut bytes = vec![];
+        bytes.resize(64, 0u8);
+
+        Challenge255::<C>::new(&bytes.try_into().unwrap())
+    }
+
+    fn common_point(&mut self, _point: C) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn common_scalar(&mut self, _scalar: C::Scalar) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<C: CurveAffine> SolidityTranscript<C> for SolidityKeccakRead<C, Challenge255<C>> {}
+
+/// `ShaRead` is generic over the `digest::Digest` it hashes with, so the real
+/// Keccak transcript reader is simply `ShaRead` parameterised with
+/// `sha3::Keccak256` — no separate reader type is needed, and this avoids
+/// depending on a `transcript::keccak256` module that does not exist.
+pub enum SolidityKeccakSelector<R: Read, C: CurveAffine, D: Digest> {
+    KeccakRead(ShaRead<R, C, Challenge255<C>, D>),
+    SolidityKeccakRead(SolidityKeccakRead<C, Challenge255<C>>),
+}
+
+impl<R: Read, C: CurveAffine, D: Digest + Clone> TranscriptRead<C, Challenge255<C>>
+    for SolidityKeccakSelector<R, C, D>
+{
+    fn read_point(&mut self) -> io::Result<C> {
+        match self {
+            SolidityKeccakSelector::KeccakRead(hasher) => hasher.read_point(),
+            SolidityKeccakSelector::SolidityKeccakRead(hasher) => hasher.read_point(),
+        }
+    }
+
+    fn read_scalar(&mut self) -> io::Result<C::Scalar> {
+        match self {
+            SolidityKeccakSelector::KeccakRead(hasher) => hasher.read_scalar(),
+            SolidityKeccakSelector::SolidityKeccakRead(hasher) => hasher.read_scalar(),
+        }
+    }
+}
+
+impl<R: Read, C: CurveAffine, D: Digest + Clone> Transcript<C, Challenge255<C>>
+    for SolidityKeccakSelector<R, C, D>
+{
+    fn squeeze_challenge(&mut self) -> Challenge255<C> {
+        match self {
+            SolidityKeccakSelector::KeccakRead(hasher) => hasher.squeeze_challenge(),
+            SolidityKeccakSelector::SolidityKeccakRead(hasher) => hasher.squeeze_challenge(),
+        }
+    }
+
+    fn common_point(&mut self, point: C) -> io::Result<()> {
+        match self {
+            SolidityKeccakSelector::KeccakRead(hasher) => hasher.common_point(point),
+            SolidityKeccakSelector::SolidityKeccakRead(hasher) => hasher.common_point(point),
+        }
+    }
+
+    fn common_scalar(&mut self, scalar: <C>::Scalar) -> io::Result<()> {
+        match self {
+            SolidityKeccakSelector::KeccakRead(hasher) => hasher.common_scalar(scalar),
+            SolidityKeccakSelector::SolidityKeccakRead(hasher) => hasher.common_scalar(scalar),
+        }
+    }
+}
+
+impl<R: Read, C: CurveAffine, D: Digest + Clone> SolidityTranscript<C>
+    for SolidityKeccakSelector<R, C, D>
+{
+}
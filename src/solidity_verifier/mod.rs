@@ -9,11 +9,41 @@ use num_bigint::BigUint;
 use tera::Tera;
 
 pub mod codegen;
+#[cfg(all(test, feature = "evm"))]
+mod evm;
+pub mod keccak256;
+pub mod sha256;
+
+/// Fiat–Shamir hash choice exposed to the template as the `transcript_hash`
+/// context value (`"sha"` or `"keccak"`).
+///
+/// SHA256 is kept for backwards compatibility, but `keccak256` is a native EVM
+/// opcode and is therefore much cheaper to recompute on-chain. The choice is
+/// not passed to `solidity_codegen_with_proof`, so the challenge values it
+/// computes off-chain are the same for both variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolidityTranscriptHash {
+    Sha,
+    Keccak,
+}
+
+/// Polynomial-commitment opening scheme the generated verifier implements.
+///
+/// `Gwc` is the multi-open where each distinct rotation set carries its own KZG
+/// opening proof. It is the only variant: the BDFG21/SHPLONK single-proof
+/// opening is not supported, and `solidity_codegen_with_proof` does not
+/// receive the scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpeningScheme {
+    Gwc,
+}
 
 pub fn solidity_render<E: MultiMillerLoop>(
     path_in: &str,
     path_out: &str,
     template_name: &str,
+    transcript_hash: SolidityTranscriptHash,
+    opening_scheme: OpeningScheme,
     target_circuit_params: &ParamsVerifier<E>,
     verify_circuit_params: &ParamsVerifier<E>,
     vkey: &VerifyingKey<E::G1Affine>,
@@ -21,8 +51,200 @@ pub fn solidity_render<E: MultiMillerLoop>(
     proofs: Vec<u8>,
 ) {
     let tera = Tera::new(path_in).unwrap();
+    let tera_ctx = build_render_context(
+        transcript_hash,
+        opening_scheme,
+        target_circuit_params,
+        verify_circuit_params,
+        vkey,
+        instances,
+        proofs,
+    );
+
+    let fd = std::fs::File::create(path_out).unwrap();
+
+    tera.render_to(template_name, &tera_ctx, fd)
+        .expect("failed to render template");
+}
+
+/// Render the verifying-key data and the verifier logic as two separate
+/// sources.
+///
+/// `solidity_render` bakes the vkey hash, G2 points, Lagrange commitments and
+/// column counts into one monolithic contract. Splitting them lets the vkey be
+/// swapped without redeploying the (larger) verifier logic, and lets several
+/// circuits share a single verifier. The `Vk` template holds all the constant
+/// data; the `Verifier` template reads it back via `extcodecopy`/an external
+/// call. Returns `(vk_source, verifier_source)`.
+///
+/// The caller must supply `vk_template_name`/`verifier_template_name` resolving
+/// to templates under `path_in`. The matching `Vk`/`Verifier` Tera templates
+/// live under `sol/templates`, outside this source snapshot; without them
+/// `tera.render` will report a missing-template error.
+pub fn solidity_render_separately<E: MultiMillerLoop>(
+    path_in: &str,
+    vk_template_name: &str,
+    verifier_template_name: &str,
+    transcript_hash: SolidityTranscriptHash,
+    opening_scheme: OpeningScheme,
+    target_circuit_params: &ParamsVerifier<E>,
+    verify_circuit_params: &ParamsVerifier<E>,
+    vkey: &VerifyingKey<E::G1Affine>,
+    instances: &Vec<E::Scalar>,
+    proofs: Vec<u8>,
+) -> (String, String) {
+    let tera = Tera::new(path_in).unwrap();
+    let tera_ctx = build_render_context(
+        transcript_hash,
+        opening_scheme,
+        target_circuit_params,
+        verify_circuit_params,
+        vkey,
+        instances,
+        proofs,
+    );
+
+    let (vk_ctx, verifier_ctx) = split_render_context(tera_ctx);
+
+    let vk = tera
+        .render(vk_template_name, &vk_ctx)
+        .expect("failed to render vk template");
+    let verifier = tera
+        .render(verifier_template_name, &verifier_ctx)
+        .expect("failed to render verifier template");
+
+    (vk, verifier)
+}
+
+/// Split the full render context into the data each of the two contracts
+/// needs.
+///
+/// The `Vk` data contract owns the pure cryptographic constants — the G2
+/// points, both sets of Lagrange commitments and the vkey `init_scalar` — which
+/// the `Verifier` reads back at runtime via `extcodecopy`/an external call;
+/// those keys are removed from the verifier context so it no longer bakes them
+/// in. The circuit-shape counters (`n_advice`/`evals`/`lookups`/
+/// `permutation_products`/`degree`) are codegen-time values that drive how much
+/// Solidity the verifier template emits (read counts and loop bounds), so they
+/// are copied into the vk context for its own declarations but deliberately
+/// *kept* in the verifier context as well. Everything else — the proof-derived
+/// values from `solidity_codegen_with_proof` and the logic flags — stays
+/// verifier-only.
+fn split_render_context(full: tera::Context) -> (tera::Context, tera::Context) {
+    const VK_G2_PREFIXES: &[&str] = &[
+        "target_circuit_s_g2",
+        "target_circuit_n_g2",
+        "verify_circuit_s_g2",
+        "verify_circuit_n_g2",
+    ];
+    // Pure constants: moved to the vk contract and stripped from the verifier.
+    const VK_EXCLUSIVE_KEYS: &[&str] = &[
+        "verify_circuit_lagrange_commitments",
+        "target_circuit_lagrange_commitments",
+        "init_scalar",
+    ];
+    // Shape counters: mirrored into the vk contract but left in the verifier,
+    // since the verifier template needs them at codegen time.
+    const VK_SHARED_KEYS: &[&str] = &[
+        "n_advice",
+        "evals",
+        "lookups",
+        "permutation_products",
+        "degree",
+    ];
+
+    let vk_g2_keys = VK_G2_PREFIXES
+        .iter()
+        .flat_map(|prefix| ["x0", "x1", "y0", "y1"].map(|c| format!("{}_{}", prefix, c)))
+        .collect::<Vec<_>>();
+
+    let exclusive_keys = VK_EXCLUSIVE_KEYS
+        .iter()
+        .map(|k| k.to_string())
+        .chain(vk_g2_keys)
+        .collect::<Vec<_>>();
+
+    let mut vk_ctx = tera::Context::new();
+    for key in exclusive_keys
+        .iter()
+        .cloned()
+        .chain(VK_SHARED_KEYS.iter().map(|k| k.to_string()))
+    {
+        if let Some(value) = full.get(&key) {
+            vk_ctx.insert(&key, value);
+        }
+    }
+
+    // Strip only the pure constants from the verifier; the shape counters stay.
+    let mut verifier_ctx = full;
+    for key in &exclusive_keys {
+        verifier_ctx.remove(key);
+    }
+
+    (vk_ctx, verifier_ctx)
+}
+
+/// Encode `instances` and `proof` into the exact byte layout the generated
+/// verifier consumes on-chain.
+///
+/// Each scalar instance is written as a 32-byte big-endian word, in the same
+/// order the Tera template reads them, followed verbatim by the proof
+/// transcript bytes. When `selector` is provided its four bytes are prepended
+/// so the result can be submitted directly as a contract method call.
+pub fn encode_calldata<E: MultiMillerLoop>(
+    instances: &Vec<E::Scalar>,
+    proof: &[u8],
+    selector: Option<[u8; 4]>,
+) -> Vec<u8> {
+    let mut calldata = Vec::with_capacity(4 + instances.len() * 32 + proof.len());
+
+    if let Some(selector) = selector {
+        calldata.extend_from_slice(&selector);
+    }
+
+    for instance in instances {
+        let bytes = field_to_bn(instance).to_bytes_be();
+        // Left-pad each scalar to a full 32-byte EVM word.
+        calldata.extend(std::iter::repeat(0u8).take(32 - bytes.len()));
+        calldata.extend_from_slice(&bytes);
+    }
+
+    calldata.extend_from_slice(proof);
+
+    calldata
+}
+
+fn build_render_context<E: MultiMillerLoop>(
+    transcript_hash: SolidityTranscriptHash,
+    opening_scheme: OpeningScheme,
+    target_circuit_params: &ParamsVerifier<E>,
+    verify_circuit_params: &ParamsVerifier<E>,
+    vkey: &VerifyingKey<E::G1Affine>,
+    instances: &Vec<E::Scalar>,
+    proofs: Vec<u8>,
+) -> tera::Context {
     let mut tera_ctx = tera::Context::new();
 
+    // Expose the requested hash to the template as a flag. Only this context
+    // value depends on `transcript_hash`: `solidity_codegen_with_proof` below
+    // is not given it, so the challenge values it computes do not change.
+    tera_ctx.insert(
+        "transcript_hash",
+        match transcript_hash {
+            SolidityTranscriptHash::Sha => "sha",
+            SolidityTranscriptHash::Keccak => "keccak",
+        },
+    );
+
+    // Expose the opening scheme to the template. Only the GWC multi-open is
+    // wired up today.
+    tera_ctx.insert(
+        "opening_scheme",
+        match opening_scheme {
+            OpeningScheme::Gwc => "gwc",
+        },
+    );
+
     let g2field_to_bn = |f: &<E::G2Affine as CurveAffine>::Base| {
         let mut bytes: Vec<u8> = Vec::new();
         f.write(&mut bytes).unwrap();
@@ -125,6 +347,8 @@ pub fn solidity_render<E: MultiMillerLoop>(
 
     tera_ctx.insert("degree", &vkey.domain.get_quotient_poly_degree());
 
+    // Count the evaluation scalars carried by the transcript for the GWC
+    // multi-open.
     let evals = vkey.cs.instance_queries.len()
         + vkey.cs.advice_queries.len()
         + vkey.cs.fixed_queries.len()
@@ -143,10 +367,50 @@ pub fn solidity_render<E: MultiMillerLoop>(
         &mut tera_ctx,
     );
 
-    let fd = std::fs::File::create(path_out).unwrap();
+    tera_ctx
+}
 
-    tera.render_to(template_name, &tera_ctx, fd)
-        .expect("failed to render template");
+#[test]
+fn test_split_render_context_partition() {
+    let mut full = tera::Context::new();
+    // vk constants and shape counters
+    full.insert("init_scalar", "123");
+    full.insert("n_advice", &4);
+    full.insert("evals", &7);
+    full.insert("target_circuit_s_g2_x0", "1");
+    full.insert("verify_circuit_n_g2_y1", "2");
+    full.insert("verify_circuit_lagrange_commitments", &vec![["1", "2"]]);
+    // verifier-owned values
+    full.insert("transcript_hash", "keccak");
+    full.insert("opening_scheme", "gwc");
+    full.insert("instances", &vec!["9"]);
+
+    let (vk, verifier) = split_render_context(full);
+
+    let vk_keys = vk.into_json();
+    let vk_keys = vk_keys.as_object().unwrap();
+    let verifier_keys = verifier.into_json();
+    let verifier_keys = verifier_keys.as_object().unwrap();
+
+    // Pure constants live only in the vk contract.
+    assert!(vk_keys.contains_key("init_scalar"));
+    assert!(vk_keys.contains_key("target_circuit_s_g2_x0"));
+    assert!(vk_keys.contains_key("verify_circuit_lagrange_commitments"));
+    assert!(!verifier_keys.contains_key("init_scalar"));
+    assert!(!verifier_keys.contains_key("target_circuit_s_g2_x0"));
+    assert!(!verifier_keys.contains_key("verify_circuit_lagrange_commitments"));
+
+    // Shape counters are mirrored into the vk contract but must remain in the
+    // verifier, which needs them for its read counts and loop bounds.
+    assert!(vk_keys.contains_key("evals"));
+    assert!(vk_keys.contains_key("n_advice"));
+    assert!(verifier_keys.contains_key("evals"));
+    assert!(verifier_keys.contains_key("n_advice"));
+
+    // Logic flags and proof data live only in the verifier contract.
+    assert!(verifier_keys.contains_key("transcript_hash"));
+    assert!(verifier_keys.contains_key("instances"));
+    assert!(!vk_keys.contains_key("transcript_hash"));
 }
 
 #[test]
@@ -222,6 +486,8 @@ pub fn test_solidity_render() {
         "sol/templates/*",
         "sol/contracts/AggregatorConfig.sol",
         "AggregatorConfig.sol.tera",
+        SolidityTranscriptHash::Keccak,
+        OpeningScheme::Gwc,
         &target_params_verifier,
         &verifier_params_verifier,
         &vkey,